@@ -1,8 +1,34 @@
+use std::any::Any;
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::parse_error::ParseError;
+
 #[derive(Debug)]
 pub struct HTMLDocument<'a> {
     pub html: Box<[HTMLNode<'a>]>,
+    ///Issues recovered from while parsing in tolerant mode; always empty in strict mode.
+    pub errors: Box<[ParseError]>,
+    mode: DocumentMode,
+}
+
+impl<'a> HTMLDocument<'a> {
+    pub(crate) fn new(
+        html: Box<[HTMLNode<'a>]>,
+        errors: Box<[ParseError]>,
+        mode: DocumentMode,
+    ) -> Self {
+        Self { html, errors, mode }
+    }
+
+    /// Whether the document renders in quirks or standards mode, as decided
+    /// by its DOCTYPE declaration (or lack of one) while parsing.
+    pub fn mode(&self) -> DocumentMode {
+        self.mode
+    }
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum DocumentMode {
     Quirks,
     Standards,
@@ -16,18 +42,38 @@ pub enum HTMLNode<'a> {
     Doctype(&'a str),
     ///HTML Comments: <!--This is a comment-->
     Comment(&'a str),
-    ///Regular text
-    Text(&'a str),
+    ///CDATA section inside foreign content: <![CDATA[ ... ]]>, never entity-decoded.
+    CData(&'a str),
+    ///Processing instruction inside foreign content: <?target body?>
+    ProcessingInstruction { target: &'a str, body: &'a str },
+    ///Regular text, with character references already decoded.
+    ///Borrowed when the source slice contains no references, owned otherwise.
+    Text(Cow<'a, str>),
     ///All other elements
     Element {
         name: &'a str,
         attributes: Box<[HTMLAttribute<'a>]>,
         children: Box<[HTMLNode<'a>]>,
+        ///Set for a foreign element when a `ForeignBodyHandler` is configured
+        ///and chose to annotate its raw body; always `None` otherwise.
+        foreign_annotation: Option<ForeignAnnotation>,
     },
 }
 
+/// Opaque, consumer-supplied data attached to a foreign element's node by a
+/// `ForeignBodyHandler`, e.g. a parsed CSS rule list or a JS token count.
+/// webless never inspects the contents; it only carries them.
+pub struct ForeignAnnotation(pub Box<dyn Any>);
+
+impl fmt::Debug for ForeignAnnotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ForeignAnnotation(..)")
+    }
+}
+
 #[derive(Debug)]
 pub struct HTMLAttribute<'a> {
     pub name: &'a str,
-    pub value: &'a str,
+    ///Attribute value with character references already decoded.
+    pub value: Cow<'a, str>,
 }
@@ -0,0 +1,239 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+
+use crate::ast::{HTMLAttribute, HTMLNode};
+use crate::parse_error::ParseError;
+use crate::parser::{ParseString, ParserConfig};
+
+/// A single parsing event, as emitted by [`EventReader`]. Mirrors the shapes
+/// found in [`crate::HTMLNode`], but produced incrementally as the source is
+/// scanned rather than assembled into a tree. Every `StartElement` is paired
+/// with exactly one later `EndElement`, even for void elements.
+#[derive(Debug)]
+pub enum Event<'a> {
+    StartElement {
+        name: &'a str,
+        attributes: Box<[HTMLAttribute<'a>]>,
+    },
+    EndElement {
+        name: &'a str,
+    },
+    ///Regular text, with character references already decoded.
+    Text(Cow<'a, str>),
+    ///HTML Comments: <!--This is a comment-->
+    Comment(&'a str),
+    ///Doctype declaration: <!DOCTYPE ...>
+    Doctype(&'a str),
+    ///Foreign text, ie. stuff inside XML, JS or CSS nodes.
+    Foreign(&'a str),
+    ///CDATA section inside foreign content: <![CDATA[ ... ]]>, never entity-decoded.
+    CData(&'a str),
+    ///Processing instruction inside foreign content: <?target body?>
+    ProcessingInstruction { target: &'a str, body: &'a str },
+}
+
+/// An open, non-void, non-foreign element still awaiting its `EndElement`.
+struct OpenFrame<'a> {
+    name: &'a str,
+}
+
+/// Parses `source` incrementally, yielding [`Event`]s instead of building a
+/// tree, for callers who want to react to markup as it is scanned rather
+/// than hold the whole document in memory.
+///
+/// Unlike [`crate::parse_with_config`], there is no separate `Result`
+/// wrapper: a malformed document surfaces as `Some(Err(..))` from `next()`,
+/// after which the reader is exhausted and always returns `None`.
+pub struct EventReader<'a> {
+    parser: ParseString<'a>,
+    stack: Vec<OpenFrame<'a>>,
+    pending: VecDeque<Event<'a>>,
+    done: bool,
+}
+
+/// Creates a reader over `source` in strict mode, mirroring [`crate::parse`]'s default.
+pub fn events(source: &str) -> EventReader<'_> {
+    EventReader::new(source, ParserConfig::default())
+}
+
+impl<'a> EventReader<'a> {
+    /// Creates a reader over `source`. `config.strict` controls whether a
+    /// missing closing tag (at EOF) or a closing tag that doesn't match any
+    /// open element ends the stream with an error or is implicitly recovered
+    /// from, matching the behaviour of [`crate::parse_with_config`].
+    pub fn new(source: &'a str, config: ParserConfig) -> Self {
+        EventReader {
+            parser: ParseString::new(source, config),
+            stack: vec![],
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn fail(&mut self, message: String) -> Option<Result<Event<'a>, ParseError>> {
+        let position = self.parser.current_index;
+        self.done = true;
+        Some(Err(self.parser.error_at(message, position)))
+    }
+
+    /// Finds the nearest open frame (from the top of the stack) whose name
+    /// matches `name`, mirroring the tree-builder's "implicit ancestor
+    /// close" recovery: everything above the match is closed along with it.
+    fn find_matching_frame(&self, name: &str) -> Option<usize> {
+        self.stack
+            .iter()
+            .rposition(|frame| frame.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Produces the next event by advancing the underlying scanner, looping
+    /// internally past constructs (like a stray closing tag in tolerant
+    /// mode) that are skipped rather than surfaced as an event.
+    fn advance_one(&mut self) -> Option<Result<Event<'a>, ParseError>> {
+        loop {
+            if self.stack.is_empty() {
+                self.parser.ignore_whitespace();
+            }
+
+            if self.parser.is_at_end() {
+                if let Some(frame) = self.stack.pop() {
+                    if self.parser.config.strict {
+                        return self.fail(format!(
+                            "Expected matching closing tag for {}",
+                            frame.name
+                        ));
+                    }
+                    return Some(Ok(Event::EndElement { name: frame.name }));
+                }
+                self.done = true;
+                return None;
+            }
+
+            let is_closing_tag =
+                self.parser.current() == Some('<') && self.parser.peek(1) == Some('/');
+
+            if is_closing_tag {
+                let start = self.parser.current_index;
+                let name = match self.parser.skip_closing_tag() {
+                    Ok(name) => name,
+                    Err(msg) => return self.fail(msg),
+                };
+
+                let Some(depth) = self.find_matching_frame(name) else {
+                    if self.parser.config.strict {
+                        self.parser.current_index = start;
+                        return self.fail(String::from("Unexpected closing tag"));
+                    }
+                    // Stray closing tag with nothing open to match it: skip and keep scanning.
+                    continue;
+                };
+
+                if self.parser.config.strict && depth + 1 != self.stack.len() {
+                    let expected = self.stack.last().unwrap().name;
+                    return self.fail(format!(
+                        "Mismatched closing tag: Expected '{}', found '{}'",
+                        expected, name
+                    ));
+                }
+
+                // Close everything from the innermost open element down to (and
+                // including) the matched one, innermost first.
+                while self.stack.len() > depth {
+                    let frame = self.stack.pop().unwrap();
+                    self.pending.push_back(Event::EndElement { name: frame.name });
+                }
+                return Some(Ok(self.pending.pop_front().unwrap()));
+            }
+
+            if self.stack.is_empty() && self.parser.current() != Some('<') {
+                // Only "strictNode"s (elements, comments, doctypes) are allowed
+                // at the document level; bare text here is a grammar error.
+                return self.fail(format!(
+                    "Expected {} '{}', found '{}'",
+                    "start of a node",
+                    '<',
+                    self.parser.current_as_string()
+                ));
+            }
+
+            if !self.stack.is_empty() && self.parser.current() != Some('<') {
+                return match self.parser.text() {
+                    Ok(HTMLNode::Text(text)) => Some(Ok(Event::Text(text))),
+                    Ok(_) => unreachable!("text() always returns HTMLNode::Text"),
+                    Err(msg) => self.fail(msg),
+                };
+            }
+
+            if self.parser.peek(1) == Some('!') {
+                return match self.parser.strict_node() {
+                    Ok(node) => Some(Ok(leaf_node_to_event(node))),
+                    Err(msg) => self.fail(msg),
+                };
+            }
+
+            // An opening tag, whether at the document root or nested: parse
+            // it ourselves instead of delegating to `element()`, so a deeply
+            // nested document doesn't need to be built into a tree before its
+            // first event can be yielded.
+            let open = match self.parser.open_tag() {
+                Ok(open) => open,
+                Err(msg) => return self.fail(msg),
+            };
+
+            if open.is_foreign {
+                let nodes = match self.parser.foreign_content(open.name) {
+                    Ok(nodes) => nodes,
+                    Err(msg) => return self.fail(msg),
+                };
+                if let Err(msg) = self.parser.skip_closing_tag() {
+                    return self.fail(msg);
+                }
+                self.pending
+                    .extend(nodes.into_iter().map(leaf_node_to_event));
+                self.pending.push_back(Event::EndElement { name: open.name });
+            } else if open.is_void {
+                self.pending.push_back(Event::EndElement { name: open.name });
+            } else {
+                self.stack.push(OpenFrame { name: open.name });
+            }
+
+            return Some(Ok(Event::StartElement {
+                name: open.name,
+                attributes: open.attributes,
+            }));
+        }
+    }
+
+}
+
+/// Converts a leaf `HTMLNode` (one with no children of its own) into the
+/// matching `Event`, for the cases where `ParseString` already hands back a
+/// fully-formed node: `strict_node()`'s `<!` branch (comments, doctypes) and
+/// `foreign_content()` (foreign text, CDATA sections, processing instructions).
+fn leaf_node_to_event(node: HTMLNode<'_>) -> Event<'_> {
+    match node {
+        HTMLNode::Comment(comment) => Event::Comment(comment),
+        HTMLNode::Doctype(doctype) => Event::Doctype(doctype),
+        HTMLNode::Foreign(text) => Event::Foreign(text),
+        HTMLNode::CData(content) => Event::CData(content),
+        HTMLNode::ProcessingInstruction { target, body } => {
+            Event::ProcessingInstruction { target, body }
+        }
+        other => {
+            unreachable!("element parsing is only reached through open_tag() directly: {other:?}")
+        }
+    }
+}
+
+impl<'a> Iterator for EventReader<'a> {
+    type Item = Result<Event<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(Ok(event));
+        }
+        if self.done {
+            return None;
+        }
+        self.advance_one()
+    }
+}
@@ -0,0 +1,12 @@
+mod ast;
+mod events;
+mod parse_error;
+mod parser;
+mod serialize;
+mod tests;
+
+pub use ast::*;
+pub use events::{events, Event, EventReader};
+pub use parse_error::ParseError;
+pub use parser::{parse, parse_with_config, ForeignBodyHandler, ParserConfig};
+pub use serialize::{SerializeMode, SerializeOptions};
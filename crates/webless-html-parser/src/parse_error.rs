@@ -0,0 +1,32 @@
+use std::fmt::Display;
+
+/// An error raised while parsing, either fatal (strict mode) or recovered
+/// from (tolerant mode, where it is collected alongside the best-effort document).
+#[derive(Debug)]
+pub struct ParseError {
+    message: String,
+    position: usize,
+}
+
+impl ParseError {
+    pub(crate) fn new(message: String, position: usize) -> Self {
+        Self { message, position }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Byte offset into the source string at which the error was raised.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
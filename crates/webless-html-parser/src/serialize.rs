@@ -0,0 +1,243 @@
+use std::borrow::Cow;
+
+use crate::ast::*;
+use crate::parser::{FOREIGN_ELEMENTS, VOID_ELEMENTS};
+
+/// Elements that conventionally render on their own line when pretty-printing.
+const BLOCK_ELEMENTS: &[&str] = &[
+    "address", "article", "aside", "blockquote", "body", "details", "dialog", "dd", "div", "dl",
+    "dt", "fieldset", "figcaption", "figure", "footer", "form", "h1", "h2", "h3", "h4", "h5",
+    "h6", "header", "hr", "html", "li", "main", "nav", "ol", "p", "pre", "section", "table", "ul",
+];
+
+/// Elements whose text content is significant down to the exact whitespace,
+/// so it must never be reindented (pretty mode) or collapsed (minified mode).
+const WHITESPACE_SENSITIVE_ELEMENTS: &[&str] = &["pre", "textarea"];
+
+/// Controls how [`HTMLDocument::to_html`] renders markup.
+#[derive(Debug, Clone, Copy)]
+pub enum SerializeMode {
+    /// Indents children and breaks lines between block elements.
+    Pretty { indent_width: usize },
+    /// Collapses insignificant whitespace and writes the tree as compactly as possible.
+    Minified,
+}
+
+/// Options controlling [`HTMLDocument::to_html`].
+#[derive(Debug, Clone, Copy)]
+pub struct SerializeOptions {
+    pub mode: SerializeMode,
+}
+
+impl Default for SerializeOptions {
+    fn default() -> Self {
+        Self {
+            mode: SerializeMode::Pretty { indent_width: 2 },
+        }
+    }
+}
+
+impl<'a> HTMLDocument<'a> {
+    /// Re-emits the document as an HTML string, per `options`.
+    pub fn to_html(&self, options: &SerializeOptions) -> String {
+        let mut out = String::new();
+        for node in self.html.iter() {
+            write_node(node, &mut out, options, 0, false);
+        }
+        out
+    }
+
+    /// Concatenates all `Text` nodes in document order, skipping foreign
+    /// (script/style/svg/math) content, comments and doctypes.
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for node in self.html.iter() {
+            collect_text(node, &mut out);
+        }
+        out
+    }
+}
+
+fn is_block_element(name: &str) -> bool {
+    BLOCK_ELEMENTS.contains(&name)
+}
+
+fn is_whitespace_sensitive(name: &str) -> bool {
+    WHITESPACE_SENSITIVE_ELEMENTS.contains(&name)
+}
+
+fn write_indent(out: &mut String, indent_width: usize, depth: usize) {
+    out.push('\n');
+    for _ in 0..(indent_width * depth) {
+        out.push(' ');
+    }
+}
+
+fn write_node(
+    node: &HTMLNode,
+    out: &mut String,
+    options: &SerializeOptions,
+    depth: usize,
+    preserve_whitespace: bool,
+) {
+    match node {
+        HTMLNode::Text(text) => match options.mode {
+            SerializeMode::Minified if !preserve_whitespace => {
+                out.push_str(&escape_text(&collapse_whitespace(text)))
+            }
+            _ => out.push_str(&escape_text(text)),
+        },
+        HTMLNode::Comment(comment) => {
+            out.push_str("<!--");
+            out.push_str(comment);
+            out.push_str("-->");
+        }
+        HTMLNode::Doctype(doctype) => {
+            out.push_str("<!DOCTYPE");
+            out.push_str(doctype);
+            out.push('>');
+        }
+        HTMLNode::Foreign(raw) => out.push_str(raw),
+        HTMLNode::CData(content) => {
+            out.push_str("<![CDATA[");
+            out.push_str(content);
+            out.push_str("]]>");
+        }
+        HTMLNode::ProcessingInstruction { target, body } => {
+            out.push_str("<?");
+            out.push_str(target);
+            out.push_str(body);
+            out.push_str("?>");
+        }
+        HTMLNode::Element {
+            name,
+            attributes,
+            children,
+            foreign_annotation: _,
+        } => {
+            let name: &str = name;
+            write_open_tag(name, attributes, out);
+
+            if VOID_ELEMENTS.contains(&name) {
+                return;
+            }
+
+            let pretty_block = match options.mode {
+                SerializeMode::Pretty { .. } => {
+                    !children.is_empty()
+                        && !FOREIGN_ELEMENTS.contains(&name)
+                        && !is_whitespace_sensitive(name)
+                        && is_block_element(name)
+                }
+                SerializeMode::Minified => false,
+            };
+            let indent_width = match options.mode {
+                SerializeMode::Pretty { indent_width } => indent_width,
+                SerializeMode::Minified => 0,
+            };
+            let preserve_whitespace = preserve_whitespace || is_whitespace_sensitive(name);
+
+            for child in children.iter() {
+                if pretty_block {
+                    write_indent(out, indent_width, depth + 1);
+                }
+                write_node(child, out, options, depth + 1, preserve_whitespace);
+            }
+            if pretty_block {
+                write_indent(out, indent_width, depth);
+            }
+
+            out.push_str("</");
+            out.push_str(name);
+            out.push('>');
+        }
+    }
+}
+
+fn write_open_tag(name: &str, attributes: &[HTMLAttribute], out: &mut String) {
+    out.push('<');
+    out.push_str(name);
+    for attribute in attributes {
+        out.push(' ');
+        out.push_str(attribute.name);
+        if !attribute.value.is_empty() {
+            out.push_str("=\"");
+            out.push_str(&escape_attribute_value(&attribute.value));
+            out.push('"');
+        }
+    }
+    out.push('>');
+}
+
+/// Escapes the characters that would otherwise be read back as a character
+/// reference or close an element early, so decoded text round-trips.
+fn escape_text(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            other => escaped.push(other),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+fn escape_attribute_value(value: &str) -> Cow<'_, str> {
+    if !value.contains(['&', '"']) {
+        return Cow::Borrowed(value);
+    }
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            other => escaped.push(other),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Collapses runs of whitespace down to a single space, matching the HTML5
+/// "insignificant whitespace" handling used by browser minifiers.
+fn collapse_whitespace(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(char::is_whitespace) {
+        return Cow::Borrowed(text);
+    }
+    let mut collapsed = String::with_capacity(text.len());
+    let mut last_was_space = false;
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !last_was_space {
+                collapsed.push(' ');
+            }
+            last_was_space = true;
+        } else {
+            collapsed.push(c);
+            last_was_space = false;
+        }
+    }
+    Cow::Owned(collapsed)
+}
+
+fn collect_text(node: &HTMLNode, out: &mut String) {
+    match node {
+        HTMLNode::Text(text) => out.push_str(text),
+        HTMLNode::Element { name, children, .. } => {
+            if FOREIGN_ELEMENTS.contains(name) {
+                return;
+            }
+            for child in children.iter() {
+                collect_text(child, out);
+            }
+        }
+        HTMLNode::Comment(_)
+        | HTMLNode::Doctype(_)
+        | HTMLNode::Foreign(_)
+        | HTMLNode::CData(_)
+        | HTMLNode::ProcessingInstruction { .. } => {}
+    }
+}
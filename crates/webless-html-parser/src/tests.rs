@@ -0,0 +1,122 @@
+#![cfg(test)]
+use super::*;
+
+#[test]
+fn decodes_named_decimal_hex_and_overflow_entities() {
+    let doc = parse("<p>&amp;&#65;&#x41;&copy;&#99999999999;&bogus;</p>").unwrap();
+    assert_eq!(doc.to_text(), "&AA\u{a9}\u{fffd}&bogus;");
+}
+
+#[test]
+fn unicode_tag_and_attribute_names_do_not_panic() {
+    let doc = parse(r#"<café 属性="値">hi</café>"#).unwrap();
+    match &doc.html[0] {
+        HTMLNode::Element { name, attributes, .. } => {
+            assert_eq!(*name, "café");
+            assert_eq!(attributes[0].name, "属性");
+            assert_eq!(attributes[0].value, "値");
+        }
+        other => panic!("expected an element, got {other:?}"),
+    }
+}
+
+#[test]
+fn serializer_round_trips_escaped_text_and_attributes() {
+    let doc = parse(r#"<p title="a &amp; b">5 &lt; 3 &amp; 4</p>"#).unwrap();
+    let html = doc.to_html(&SerializeOptions {
+        mode: SerializeMode::Minified,
+    });
+    assert_eq!(html, r#"<p title="a &amp; b">5 &lt; 3 &amp; 4</p>"#);
+    assert!(parse(&html).is_ok());
+}
+
+#[test]
+fn serializer_preserves_whitespace_in_pre() {
+    let doc = parse("<pre>line1\n  indented\nline3</pre>").unwrap();
+
+    let pretty = doc.to_html(&SerializeOptions::default());
+    assert_eq!(pretty, "<pre>line1\n  indented\nline3</pre>");
+
+    let minified = doc.to_html(&SerializeOptions {
+        mode: SerializeMode::Minified,
+    });
+    assert_eq!(minified, "<pre>line1\n  indented\nline3</pre>");
+}
+
+#[test]
+fn tolerant_mode_recovers_from_a_stray_closing_tag() {
+    // `</div>` doesn't match the open `<p>`, so `<p>` is implicitly closed and
+    // the stray tag (along with the orphaned text after it) is dropped while
+    // collecting the mismatch as a recovered error rather than a fatal one.
+    let doc = parse_with_config(
+        "<p>hi</div>still here</p>",
+        &ParserConfig {
+            strict: false,
+            ..ParserConfig::default()
+        },
+    )
+    .unwrap();
+    assert!(!doc.errors.is_empty());
+    assert_eq!(doc.to_text(), "hi");
+}
+
+#[test]
+fn event_reader_streams_top_level_elements_without_panicking() {
+    let evts: Vec<_> = events("<!--c--><div>a<b>b</b></div>")
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(matches!(evts[0], Event::Comment("c")));
+    assert!(matches!(&evts[1], Event::StartElement { name, .. } if *name == "div"));
+    assert!(matches!(evts.last().unwrap(), Event::EndElement { name } if *name == "div"));
+}
+
+#[test]
+fn doctype_sets_standards_vs_quirks_mode() {
+    let standards = parse("<!DOCTYPE html><p>hi</p>").unwrap();
+    assert!(matches!(standards.mode(), DocumentMode::Standards));
+
+    let quirks = parse("<p>hi</p>").unwrap();
+    assert!(matches!(quirks.mode(), DocumentMode::Quirks));
+}
+
+#[test]
+fn foreign_body_can_contain_cdata_and_processing_instructions() {
+    let doc = parse("<svg><![CDATA[raw <data>]]><?pi target?></svg>").unwrap();
+    match &doc.html[0] {
+        HTMLNode::Element { children, .. } => {
+            assert!(matches!(children[0], HTMLNode::CData("raw <data>")));
+            assert!(matches!(
+                children[1],
+                HTMLNode::ProcessingInstruction { target: "pi", body: " target" }
+            ));
+        }
+        other => panic!("expected an element, got {other:?}"),
+    }
+}
+
+#[test]
+fn foreign_body_handler_receives_the_raw_body() {
+    fn count_chars(_name: &str, body: &str) -> Option<Box<dyn std::any::Any>> {
+        Some(Box::new(body.len()))
+    }
+
+    let doc = parse_with_config(
+        "<script>const x = 1;</script>",
+        &ParserConfig {
+            strict: true,
+            foreign_body_handler: Some(count_chars),
+        },
+    )
+    .unwrap();
+
+    match &doc.html[0] {
+        HTMLNode::Element {
+            foreign_annotation: Some(annotation),
+            ..
+        } => {
+            let len = annotation.0.downcast_ref::<usize>().unwrap();
+            assert_eq!(*len, "const x = 1;".len());
+        }
+        other => panic!("expected an annotated foreign element, got {other:?}"),
+    }
+}
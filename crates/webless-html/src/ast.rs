@@ -1,16 +1,32 @@
+use std::borrow::Cow;
+
+use crate::parse_error::ParseError;
+
 #[derive(Debug)]
 
 pub struct HTMLDocument<'a> {
     html: Box<[HTMLNode<'a>]>,
+    errors: Box<[ParseError]>,
 }
 
 impl<'a> HTMLDocument<'a> {
-    pub(crate) fn new(html: Box<[HTMLNode<'a>]>) -> Self {
-        Self { html }
+    pub(crate) fn new(html: Box<[HTMLNode<'a>]>, errors: Box<[ParseError]>) -> Self {
+        Self { html, errors }
     }
     pub fn html(&self) -> &[HTMLNode<'a>] {
         &self.html
     }
+    /// Issues recovered from while parsing in lenient mode; always empty
+    /// in strict mode, since the first one aborts parsing with `Err`.
+    pub fn errors(&self) -> &[ParseError] {
+        &self.errors
+    }
+
+    /// Serializes the document back to well-formed HTML; see
+    /// `crate::visit::serialize`.
+    pub fn to_html(&self) -> String {
+        crate::visit::serialize(&self.html)
+    }
 }
 
 pub enum DocumentMode {
@@ -26,8 +42,12 @@ pub enum HTMLNode<'a> {
     Doctype(&'a str),
     ///HTML Comments: <!--This is a comment-->
     Comment(&'a str),
-    ///Regular text
-    Text(&'a str),
+    ///CDATA section: <![CDATA[ ... ]]>, never entity-decoded
+    CData(&'a str),
+    ///Processing instruction: <?target data?>
+    ProcessingInstruction { target: &'a str, data: &'a str },
+    ///Regular text, with character references decoded
+    Text(Cow<'a, str>),
     ///All other elements
     Element {
         name: &'a str,
@@ -39,5 +59,5 @@ pub enum HTMLNode<'a> {
 #[derive(Debug)]
 pub struct HTMLAttribute<'a> {
     pub name: &'a str,
-    pub value: &'a str,
+    pub value: Cow<'a, str>,
 }
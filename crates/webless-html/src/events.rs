@@ -0,0 +1,35 @@
+use std::borrow::Cow;
+
+use crate::ast::HTMLAttribute;
+
+/// One node-level event pulled from the input by `EventReader`, without the
+/// containing document or any ancestor elements having been materialized
+/// into a tree. Mirrors the leaf kinds of `HTMLNode`, with `StartElement`/
+/// `EndElement` standing in for `HTMLNode::Element`'s `children`.
+#[derive(Debug)]
+pub enum Event<'a> {
+    ///An opening tag; always eventually followed by a matching
+    ///`EndElement` with the same name, even for void and foreign elements.
+    StartElement {
+        name: &'a str,
+        attributes: Box<[HTMLAttribute<'a>]>,
+    },
+    ///The end of the element most recently opened by an unmatched
+    ///`StartElement`.
+    EndElement { name: &'a str },
+    ///Regular text, with character references decoded, as `HTMLNode::Text`.
+    Text(Cow<'a, str>),
+    ///Foreign text, raw and undecoded, as `HTMLNode::Foreign`.
+    Foreign(&'a str),
+    ///An HTML comment, as `HTMLNode::Comment`.
+    Comment(&'a str),
+    ///A DOCTYPE declaration, as `HTMLNode::Doctype`.
+    Doctype(&'a str),
+    ///A CDATA section, as `HTMLNode::CData`.
+    CData(&'a str),
+    ///A processing instruction, as `HTMLNode::ProcessingInstruction`.
+    ProcessingInstruction { target: &'a str, data: &'a str },
+    ///The end of the document; yielded exactly once, after which the
+    ///iterator yields `None`.
+    Eof,
+}
@@ -0,0 +1,14 @@
+mod ast;
+mod events;
+mod lossless;
+mod parse_error;
+mod parser;
+mod tests;
+mod visit;
+
+pub use ast::*;
+pub use events::Event;
+pub use lossless::{LosslessAttribute, LosslessDocument, LosslessNode};
+pub use parse_error::{ParseError, Span};
+pub use parser::{parse, parse_events, parse_lossless, parse_with_options, EventReader, ParseOptions};
+pub use visit::{collect_text, serialize, walk, Visitor};
@@ -0,0 +1,108 @@
+//! A lossless, trivia-preserving parse tree, opt-in via `parse_lossless()`.
+//! Every node retains the exact whitespace slice that preceded it, and
+//! elements retain the exact byte ranges of their opening tag, inner
+//! content and closing tag, so `LosslessDocument::to_source()` can
+//! reconstruct the original input byte-for-byte. The regular `parse()`/
+//! `parse_with_options()` entry points don't pay for any of this bookkeeping.
+
+/// An attribute as it appeared in the source, including the whitespace that
+/// preceded it and its raw (undecoded) value text.
+#[derive(Debug)]
+pub struct LosslessAttribute<'a> {
+    pub leading_trivia: &'a str,
+    pub name: &'a str,
+    /// The attribute exactly as written: `name`, or `name="value"` /
+    /// `name='value'` / `name=value`, including the `=` and any quotes.
+    pub raw: &'a str,
+}
+
+#[derive(Debug)]
+pub enum LosslessNode<'a> {
+    ///Foreign text, raw and undecoded, as `HTMLNode::Foreign`.
+    Foreign { leading_trivia: &'a str, raw: &'a str },
+    ///Doctype declaration, as `HTMLNode::Doctype`.
+    Doctype { leading_trivia: &'a str, raw: &'a str },
+    ///HTML comment, as `HTMLNode::Comment`.
+    Comment { leading_trivia: &'a str, raw: &'a str },
+    ///CDATA section, as `HTMLNode::CData`.
+    CData { leading_trivia: &'a str, raw: &'a str },
+    ///Processing instruction, as `HTMLNode::ProcessingInstruction`.
+    ProcessingInstruction {
+        leading_trivia: &'a str,
+        target: &'a str,
+        data: &'a str,
+        raw: &'a str,
+    },
+    ///Regular text, raw and undecoded (unlike `HTMLNode::Text`, which decodes
+    ///character references and so can no longer reproduce the source).
+    Text { leading_trivia: &'a str, raw: &'a str },
+    ///All other elements.
+    Element {
+        leading_trivia: &'a str,
+        name: &'a str,
+        attributes: Box<[LosslessAttribute<'a>]>,
+        ///Exact source text of the opening tag, e.g. `<a href="x">`.
+        open_tag: &'a str,
+        children: Box<[LosslessNode<'a>]>,
+        ///Exact source text between the opening and closing tags.
+        inner_content: &'a str,
+        ///Exact source text of the closing tag, e.g. `</a>`; empty for void
+        ///elements, which have none.
+        close_tag: &'a str,
+    },
+}
+
+impl<'a> LosslessNode<'a> {
+    fn write_source(&self, out: &mut String) {
+        match self {
+            LosslessNode::Foreign { leading_trivia, raw }
+            | LosslessNode::Doctype { leading_trivia, raw }
+            | LosslessNode::Comment { leading_trivia, raw }
+            | LosslessNode::CData { leading_trivia, raw }
+            | LosslessNode::Text { leading_trivia, raw }
+            | LosslessNode::ProcessingInstruction {
+                leading_trivia, raw, ..
+            } => {
+                out.push_str(leading_trivia);
+                out.push_str(raw);
+            }
+            LosslessNode::Element {
+                leading_trivia,
+                open_tag,
+                inner_content,
+                close_tag,
+                ..
+            } => {
+                out.push_str(leading_trivia);
+                out.push_str(open_tag);
+                out.push_str(inner_content);
+                out.push_str(close_tag);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LosslessDocument<'a> {
+    nodes: Box<[LosslessNode<'a>]>,
+}
+
+impl<'a> LosslessDocument<'a> {
+    pub(crate) fn new(nodes: Box<[LosslessNode<'a>]>) -> Self {
+        Self { nodes }
+    }
+
+    pub fn nodes(&self) -> &[LosslessNode<'a>] {
+        &self.nodes
+    }
+
+    /// Reconstructs the original source byte-for-byte by concatenating each
+    /// node's leading trivia and source span, recursively.
+    pub fn to_source(&self) -> String {
+        let mut out = String::new();
+        for node in &self.nodes {
+            node.write_source(&mut out);
+        }
+        out
+    }
+}
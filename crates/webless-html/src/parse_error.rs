@@ -1,42 +1,51 @@
 use std::fmt::Display;
 
+/// A half-open byte range into the source string, recorded at the point an
+/// error was raised (so typically zero-width: `start == end`).
+#[derive(Debug, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
 #[derive(Debug)]
 pub struct ParseError {
-    msg: String,
-    line: usize,
-    col: usize,
+    message: String,
+    span: Span,
 }
 
 impl ParseError {
-    pub(crate) fn new(msg: String, source: &str, byte_idx: usize) -> Self {
-        // Calculate line and column from byte index of last newline character
-        let last_newline = source[0..byte_idx]
-            .as_bytes()
-            .iter()
-            .enumerate()
-            .filter(|(_, byte)| byte == &&b'\n')
-            .enumerate()
-            .last()
-            .unwrap_or((0, (0, &0)));
-
-        let line = last_newline.0;
-        let col = byte_idx - last_newline.1 .0;
-
-        Self { msg, line, col }
+    pub(crate) fn new(message: String, span: Span) -> Self {
+        Self { message, span }
     }
 
-    pub fn message(&self) -> &String {
-        &self.msg
+    pub fn message(&self) -> &str {
+        &self.message
     }
 
-    pub fn line_and_column(&self) -> (usize, usize) {
-        (self.line, self.col)
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// Computes the 0-indexed (line, column) of `self.span().start` within
+    /// `source`, counting columns in chars rather than bytes to stay UTF-8
+    /// correct. Computed lazily so raising an error stays cheap.
+    pub fn line_and_column(&self, source: &str) -> (usize, usize) {
+        let offset = self.span.start;
+        let before = &source[..offset];
+
+        let line = before.matches('\n').count();
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let col = source[line_start..offset].chars().count();
+
+        (line, col)
     }
 }
 
 impl Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let (line, col) = self.line_and_column();
-        write!(f, "[{}:{}] {}", line, col, self.message())
+        write!(f, "[{}..{}] {}", self.span.start, self.span.end, self.message)
     }
 }
+
+impl std::error::Error for ParseError {}
@@ -1,13 +1,18 @@
+use std::borrow::Cow;
 use std::ops::Range;
 
 use crate::ast::*;
+use crate::events::Event;
+use crate::lossless::{LosslessAttribute, LosslessDocument, LosslessNode};
+use crate::parse_error::{ParseError, Span};
 
 /// Foreign elements; elements that are not expected to contain HTML,
 /// Meaning the parser will treat their inner text as a HtmlNode::Foreign.
-const FOREIGN_ELEMENTS: [&str; 6] = ["script", "style", "title", "textarea", "svg", "math"];
+pub(crate) const FOREIGN_ELEMENTS: [&str; 6] =
+    ["script", "style", "title", "textarea", "svg", "math"];
 
 /// Self-closing elements; no children or matching closing tag.
-const VOID_ELEMENTS: [&str; 16] = [
+pub(crate) const VOID_ELEMENTS: [&str; 16] = [
     "area", "base", "br", "col", "command", "embed", "hr", "img", "input", "keygen", "link",
     "meta", "param", "source", "track", "wbr",
 ];
@@ -27,52 +32,636 @@ macro_rules! control_chars {
 }
 
 /// Helper to test that a string is in a list, ignoring ascii case
-fn contains_ignore_ascii_case(list: &[&str], str: &str) -> bool {
+pub(crate) fn contains_ignore_ascii_case(list: &[&str], str: &str) -> bool {
     list.iter().any(|term| term.eq_ignore_ascii_case(str))
 }
 
-pub fn parse(source: &str) -> HTMLDocument {
-    ParseString::new(source).parse()
+/// Controls how the parser reacts to malformed input.
+pub struct ParseOptions {
+    /// When `true` (the default), the first problem encountered aborts
+    /// parsing with `Err`. When `false`, the parser recovers where it can
+    /// and collects what it couldn't fix into `HTMLDocument::errors`.
+    pub strict: bool,
 }
 
-/// Internal utility type representing the details of a parse error.
-type InternalParseError = String;
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self { strict: true }
+    }
+}
+
+/// Parses `source` in strict mode, failing on the first malformed construct.
+pub fn parse(source: &str) -> Result<HTMLDocument<'_>, ParseError> {
+    parse_with_options(source, &ParseOptions::default())
+}
+
+/// Parses `source` with the given options. In lenient mode
+/// (`options.strict == false`) this only returns `Err` for input the parser
+/// cannot make any progress on; recovered issues are instead collected into
+/// the returned document's `errors`.
+pub fn parse_with_options<'a>(
+    source: &'a str,
+    options: &ParseOptions,
+) -> Result<HTMLDocument<'a>, ParseError> {
+    ParseString::new(source, options).parse()
+}
+
+/// Parses `source` into a trivia-preserving `LosslessDocument`, whose
+/// `to_source()` can reconstruct the input byte-for-byte. Aborts on the
+/// first malformed construct, like strict `parse()`; there's no lenient
+/// lossless mode.
+pub fn parse_lossless(source: &str) -> Result<LosslessDocument<'_>, ParseError> {
+    ParseString::new(source, &ParseOptions::default()).lossless_parse()
+}
+
+/// Strict options shared by every `EventReader` returned from
+/// `parse_events()`, since there's no lenient streaming mode.
+static STRICT_OPTIONS: ParseOptions = ParseOptions { strict: true };
+
+/// Pulls events from `source` one at a time instead of materializing a tree,
+/// so a caller that only needs part of the document (the `<title>`, say)
+/// can stop early, and documents too large to hold as a tree can still be
+/// processed. Like `parse()`, the first malformed construct ends the stream
+/// with an `Err`; there's no lenient event-based mode.
+pub fn parse_events(source: &str) -> EventReader<'_, 'static> {
+    EventReader::from_parser(ParseString::new(source, &STRICT_OPTIONS))
+}
+
+/// Internal utility type representing the details of a parse error; shares
+/// its shape with the public `ParseError` so no conversion is needed at the
+/// `parse()` boundary.
+type InternalParseError = ParseError;
 /// Internal utility type representing result returned by node parsing functions that can fail.
 type NodeResult<'a> = Result<HTMLNode<'a>, InternalParseError>;
 /// Internal utility type representing result returned by attribute parsing functions that can fail.
 type AttributeResult<'a> = Result<HTMLAttribute<'a>, InternalParseError>;
 
-struct ParseString<'a> {
+/// An element whose opening tag has been read but whose closing tag hasn't,
+/// tracked on an explicit stack in lenient mode rather than via recursion so
+/// that a mismatched or missing closing tag can pop and auto-close it.
+struct OpenElement<'a> {
+    name: &'a str,
+    attributes: Box<[HTMLAttribute<'a>]>,
+    children: Vec<HTMLNode<'a>>,
+}
+
+impl<'a> OpenElement<'a> {
+    fn into_node(self) -> HTMLNode<'a> {
+        HTMLNode::Element {
+            name: self.name,
+            attributes: self.attributes,
+            children: self.children.into_boxed_slice(),
+        }
+    }
+}
+
+/// Pushes `node` into the children of the innermost open element, or the
+/// document root if nothing is currently open.
+fn push_node<'a>(stack: &mut [OpenElement<'a>], root: &mut Vec<HTMLNode<'a>>, node: HTMLNode<'a>) {
+    match stack.last_mut() {
+        Some(open) => open.children.push(node),
+        None => root.push(node),
+    }
+}
+
+/// The result of reading an opening `<...>` tag in lenient mode.
+enum OpenedTag<'a> {
+    /// A complete node: a void element, or a foreign element whose body has
+    /// already been consumed.
+    Leaf(HTMLNode<'a>),
+    /// A container element whose children and closing tag are still ahead;
+    /// pushed onto the open-element stack.
+    Container {
+        name: &'a str,
+        attributes: Box<[HTMLAttribute<'a>]>,
+    },
+}
+
+struct ParseString<'a, 'cfg> {
     source: &'a str,
     current_index: usize,
+    options: &'cfg ParseOptions,
+    errors: Vec<ParseError>,
 }
 
-impl<'a> ParseString<'a> {
-    fn new(source: &'a str) -> Self {
+impl<'a, 'cfg> ParseString<'a, 'cfg> {
+    fn new(source: &'a str, options: &'cfg ParseOptions) -> Self {
         ParseString {
             source,
             current_index: 0,
+            options,
+            errors: vec![],
+        }
+    }
+
+    /// Builds an internal error at the current cursor position, recorded as
+    /// a zero-width span.
+    fn err(&self, message: impl Into<String>) -> InternalParseError {
+        ParseError::new(
+            message.into(),
+            Span {
+                start: self.current_index,
+                end: self.current_index,
+            },
+        )
+    }
+
+    /// Parses the whole document. Lenient options are handed to
+    /// `lenient_parse`; in strict mode the first malformed
+    /// construct aborts with `Err`. Built as a thin consumer of the same
+    /// event stream `parse_events()` exposes, pushing each `StartElement`
+    /// onto a stack and attaching it to its parent once the matching
+    /// `EndElement` arrives.
+    fn parse(self) -> Result<HTMLDocument<'a>, ParseError> {
+        if !self.options.strict {
+            let mut this = self;
+            return Ok(this.lenient_parse());
         }
+
+        let mut stack: Vec<OpenElement<'a>> = vec![];
+        let mut root: Vec<HTMLNode<'a>> = vec![];
+
+        let mut events = EventReader::from_parser(self);
+        loop {
+            match events.next_event()? {
+                Event::Eof => break,
+                Event::StartElement { name, attributes } => {
+                    stack.push(OpenElement {
+                        name,
+                        attributes,
+                        children: vec![],
+                    });
+                }
+                Event::EndElement { .. } => {
+                    let open = stack
+                        .pop()
+                        .expect("EventReader only emits EndElement for a still-open element");
+                    let node = open.into_node();
+                    push_node(&mut stack, &mut root, node);
+                }
+                Event::Text(text) => push_node(&mut stack, &mut root, HTMLNode::Text(text)),
+                Event::Foreign(raw) => push_node(&mut stack, &mut root, HTMLNode::Foreign(raw)),
+                Event::Comment(raw) => push_node(&mut stack, &mut root, HTMLNode::Comment(raw)),
+                Event::Doctype(raw) => push_node(&mut stack, &mut root, HTMLNode::Doctype(raw)),
+                Event::CData(raw) => push_node(&mut stack, &mut root, HTMLNode::CData(raw)),
+                Event::ProcessingInstruction { target, data } => push_node(
+                    &mut stack,
+                    &mut root,
+                    HTMLNode::ProcessingInstruction { target, data },
+                ),
+            }
+        }
+
+        Ok(HTMLDocument::new(root.into_boxed_slice(), Box::new([])))
     }
 
-    fn parse(&mut self) -> HTMLDocument<'a> {
-        let mut html_nodes = vec![];
+    /// Parses the whole document in lenient mode: problems are recorded into
+    /// `self.errors` and recovered from rather than aborting, tracking open
+    /// elements on an explicit stack so a bad closing tag can unwind it.
+    fn lenient_parse(&mut self) -> HTMLDocument<'a> {
+        let mut stack: Vec<OpenElement<'a>> = vec![];
+        let mut root: Vec<HTMLNode<'a>> = vec![];
 
         while !self.is_at_end() {
-            html_nodes.push(self.strict_node().unwrap());
+            self.ignore_whitespace();
+            if self.is_at_end() {
+                break;
+            }
+
+            if self.next_match(b"</") {
+                self.lenient_closing_tag(&mut stack, &mut root);
+                continue;
+            }
+
+            if self.current_matches(b'<') {
+                if let Some(node) = self.lenient_open(&mut stack) {
+                    push_node(&mut stack, &mut root, node);
+                }
+                continue;
+            }
+
+            if let Some(control_chars!()) = self.current() {
+                // Can't be folded into text (which stops at control characters),
+                // so skip it explicitly to guarantee forward progress.
+                let err = self.err("Ignoring unexpected control character");
+                self.errors.push(err);
+                self.advance();
+                continue;
+            }
+
+            let node = self.lenient_text();
+            push_node(&mut stack, &mut root, node);
         }
 
-        HTMLDocument {
-            html: html_nodes.into_boxed_slice(),
+        // Anything still open at EOF is implicitly closed where it stands.
+        while let Some(open) = stack.pop() {
+            let err = self.err(format!(
+                "Unexpected end of input: auto-closing <{}>",
+                open.name
+            ));
+            self.errors.push(err);
+            let node = open.into_node();
+            push_node(&mut stack, &mut root, node);
         }
+
+        HTMLDocument::new(
+            root.into_boxed_slice(),
+            std::mem::take(&mut self.errors).into_boxed_slice(),
+        )
+    }
+
+    /// Reads a `</name>` closing tag and unwinds the open-element stack up to
+    /// its matching ancestor, auto-closing anything opened after it. A
+    /// closing tag with no open ancestor of that name is dropped as stray.
+    fn lenient_closing_tag(&mut self, stack: &mut Vec<OpenElement<'a>>, root: &mut Vec<HTMLNode<'a>>) {
+        let start = self.current_index;
+        // consume </
+        self.current_index += 2;
+
+        let name = match self.consume_alphanumeric() {
+            Ok(range) => &self.source[range],
+            Err(_) => {
+                let err = self.err("Expected a tag name after '</'");
+                self.errors.push(err);
+                return;
+            }
+        };
+        self.ignore_whitespace();
+        if self.expect("end of closing tag", b'>').is_err() {
+            let err = self.err(format!("Expected '>' to close '</{name}'"));
+            self.errors.push(err);
+            return;
+        }
+        self.advance();
+
+        match stack
+            .iter()
+            .rposition(|open| open.name.eq_ignore_ascii_case(name))
+        {
+            Some(pos) => {
+                while stack.len() > pos + 1 {
+                    let skipped = stack.pop().unwrap();
+                    let err = ParseError::new(
+                        format!(
+                            "Implicitly closing '<{}>' before mismatched closing tag '</{}>'",
+                            skipped.name, name
+                        ),
+                        Span { start, end: start },
+                    );
+                    self.errors.push(err);
+                    let node = skipped.into_node();
+                    push_node(stack, root, node);
+                }
+                let open = stack.pop().unwrap();
+                let node = open.into_node();
+                push_node(stack, root, node);
+            }
+            None => {
+                let err = ParseError::new(
+                    format!("Ignoring stray closing tag '</{name}>'"),
+                    Span { start, end: start },
+                );
+                self.errors.push(err);
+            }
+        }
+    }
+
+    /// Reads whatever follows a `<` in lenient mode: a comment, a DOCTYPE, or
+    /// an element's opening tag. Anything that fails to parse is recorded as
+    /// an error and the `<` is instead treated as a literal text character,
+    /// so the rest of the input gets another chance on the next iteration.
+    fn lenient_open(&mut self, stack: &mut Vec<OpenElement<'a>>) -> Option<HTMLNode<'a>> {
+        let start = self.current_index;
+
+        if self.peek(1).is_none() {
+            self.advance();
+            return Some(HTMLNode::Text(Cow::Borrowed(&self.source[start..self.current_index])));
+        }
+
+        if self.peek(1) == Some(b'!') {
+            let result = if self.next_match(b"<![CDATA[") {
+                self.cdata_section()
+            } else if self.peek(2) == Some(b'-') {
+                self.comment()
+            } else {
+                self.doctype_declaration()
+            };
+            return match result {
+                Ok(node) => Some(node),
+                Err(e) => Some(self.recover_as_stray_text(start, e)),
+            };
+        }
+
+        if self.peek(1) == Some(b'?') {
+            return match self.processing_instruction() {
+                Ok(node) => Some(node),
+                Err(e) => Some(self.recover_as_stray_text(start, e)),
+            };
+        }
+
+        match self.lenient_element_open() {
+            Ok(OpenedTag::Leaf(node)) => Some(node),
+            Ok(OpenedTag::Container { name, attributes }) => {
+                stack.push(OpenElement {
+                    name,
+                    attributes,
+                    children: vec![],
+                });
+                None
+            }
+            Err(e) => Some(self.recover_as_stray_text(start, e)),
+        }
+    }
+
+    /// Records `e` and rewinds to `start`, treating the `<` there as a single
+    /// literal text character so the rest of the input is reparsed fresh.
+    fn recover_as_stray_text(&mut self, start: usize, e: InternalParseError) -> HTMLNode<'a> {
+        self.errors.push(e);
+        self.current_index = start;
+        self.advance();
+        HTMLNode::Text(Cow::Borrowed(&self.source[start..self.current_index]))
+    }
+
+    /// Reads an element's opening tag (name, attributes, and for void or
+    /// foreign elements its whole body and closing tag) the same way
+    /// `EventReader::open_element` reads an opening tag, except
+    /// a duplicate attribute keeps the first value and records a warning
+    /// instead of aborting the tag.
+    fn lenient_element_open(&mut self) -> Result<OpenedTag<'a>, InternalParseError> {
+        // consume <
+        self.advance();
+        let element_name = &self.source[self.consume_alphanumeric()?];
+        self.ignore_whitespace();
+
+        let mut attributes: Vec<HTMLAttribute<'a>> = vec![];
+        while !self.current_matches(b'>') && !self.current_matches(b'/') {
+            if self.current().is_none() {
+                return Err(self.err(format!(
+                    "Expected matching closing tag for {}",
+                    element_name
+                )));
+            }
+            let attribute = self.attribute()?;
+            if attributes.iter().any(|a| a.name == attribute.name) {
+                let err = self.err(format!(
+                    "Duplicate attribute '{}', keeping the first value",
+                    attribute.name
+                ));
+                self.errors.push(err);
+            } else {
+                attributes.push(attribute);
+            }
+            self.ignore_whitespace();
+        }
+
+        if contains_ignore_ascii_case(&VOID_ELEMENTS, element_name) {
+            if self.current_matches(b'/') {
+                self.advance();
+            }
+            self.expect("end of opening tag", b'>')?;
+            self.advance();
+
+            return Ok(OpenedTag::Leaf(HTMLNode::Element {
+                name: element_name,
+                attributes: attributes.into_boxed_slice(),
+                children: Box::new([]),
+            }));
+        }
+
+        self.expect("end of opening tag", b'>')?;
+        self.advance();
+
+        if contains_ignore_ascii_case(&FOREIGN_ELEMENTS, element_name) {
+            let body = self.foreign_text(element_name)?;
+            // consume the </element_name> that foreign_text() stopped at
+            self.current_index += 2;
+            self.consume_alphanumeric()?;
+            self.ignore_whitespace();
+            self.expect("end of closing tag", b'>')?;
+            self.advance();
+            return Ok(OpenedTag::Leaf(HTMLNode::Element {
+                name: element_name,
+                attributes: attributes.into_boxed_slice(),
+                children: Box::new([body]),
+            }));
+        }
+
+        Ok(OpenedTag::Container {
+            name: element_name,
+            attributes: attributes.into_boxed_slice(),
+        })
+    }
+
+    /// Lenient counterpart to `text()`: an unrecognized character reference
+    /// is recorded as a warning rather than aborting, and the text is kept
+    /// undecoded.
+    fn lenient_text(&mut self) -> HTMLNode<'a> {
+        let raw = self.scan_text_raw();
+        match self.decode_entities(raw) {
+            Ok(text) => HTMLNode::Text(text),
+            Err(e) => {
+                self.errors.push(e);
+                HTMLNode::Text(Cow::Borrowed(raw))
+            }
+        }
+    }
+
+    /// Parses the whole document in lossless mode, where every node keeps
+    /// the exact whitespace that preceded it.
+    fn lossless_parse(mut self) -> Result<LosslessDocument<'a>, ParseError> {
+        let mut nodes = vec![];
+        while !self.is_at_end() {
+            nodes.push(self.lossless_node()?);
+        }
+        Ok(LosslessDocument::new(nodes.into_boxed_slice()))
+    }
+
+    /// Lossless counterpart to `EventReader`'s strict traversal: records the
+    /// whitespace consumed before the construct as `leading_trivia`, then
+    /// reuses the existing grammar functions (which only decode values, not
+    /// reconstruct source) purely to advance the cursor, and slices the raw
+    /// text they consumed from the source.
+    fn lossless_node(&mut self) -> Result<LosslessNode<'a>, ParseError> {
+        let trivia_start = self.current_index;
+        self.ignore_whitespace();
+        let leading_trivia = &self.source[trivia_start..self.current_index];
+
+        if !self.current_matches(b'<') {
+            let text_start = self.current_index;
+            self.text()?;
+            return Ok(LosslessNode::Text {
+                leading_trivia,
+                raw: &self.source[text_start..self.current_index],
+            });
+        }
+
+        let construct_start = self.current_index;
+        match self.peek(1) {
+            None => Err(self.err("Expected something after start of node")),
+            Some(b'!') => {
+                if self.next_match(b"<![CDATA[") {
+                    self.cdata_section()?;
+                    Ok(LosslessNode::CData {
+                        leading_trivia,
+                        raw: &self.source[construct_start..self.current_index],
+                    })
+                } else if self.peek(2) == Some(b'-') {
+                    self.comment()?;
+                    Ok(LosslessNode::Comment {
+                        leading_trivia,
+                        raw: &self.source[construct_start..self.current_index],
+                    })
+                } else {
+                    self.doctype_declaration()?;
+                    Ok(LosslessNode::Doctype {
+                        leading_trivia,
+                        raw: &self.source[construct_start..self.current_index],
+                    })
+                }
+            }
+            Some(b'?') => match self.processing_instruction()? {
+                HTMLNode::ProcessingInstruction { target, data } => {
+                    Ok(LosslessNode::ProcessingInstruction {
+                        leading_trivia,
+                        target,
+                        data,
+                        raw: &self.source[construct_start..self.current_index],
+                    })
+                }
+                _ => unreachable!("processing_instruction() only ever returns ProcessingInstruction"),
+            },
+            _ => self.lossless_element(leading_trivia, construct_start),
+        }
+    }
+
+    /// Lossless counterpart to `EventReader::open_element` and `close_tag`,
+    /// reading the whole element at once: in addition to the structured
+    /// attributes and children, keeps each attribute's leading trivia and
+    /// the exact source text of the opening tag, inner content, and closing
+    /// tag, so the element reconstructs byte-for-byte.
+    fn lossless_element(
+        &mut self,
+        leading_trivia: &'a str,
+        tag_start: usize,
+    ) -> Result<LosslessNode<'a>, ParseError> {
+        // consume <
+        self.advance();
+        let name = &self.source[self.consume_alphanumeric()?];
+
+        let mut attributes: Vec<LosslessAttribute<'a>> = vec![];
+        loop {
+            let attr_trivia_start = self.current_index;
+            self.ignore_whitespace();
+            if self.current_matches(b'>') || self.current_matches(b'/') {
+                break;
+            }
+            let attr_leading_trivia = &self.source[attr_trivia_start..self.current_index];
+            let attr_start = self.current_index;
+            let attribute = self.attribute()?;
+            // attribute() skips whitespace after a valueless name; hand it back
+            // so it becomes the next attribute's leading trivia.
+            let raw = self.source[attr_start..self.current_index]
+                .trim_end_matches([' ', '\n', '\r', '\t', '\x0C']);
+            self.current_index = attr_start + raw.len();
+
+            if attributes.iter().any(|a| a.name == attribute.name) {
+                return Err(self.err("Element has two attributes with the same name"));
+            }
+
+            attributes.push(LosslessAttribute {
+                leading_trivia: attr_leading_trivia,
+                name: attribute.name,
+                raw,
+            });
+        }
+
+        let is_void = contains_ignore_ascii_case(&VOID_ELEMENTS, name);
+
+        if is_void {
+            if self.current_matches(b'/') {
+                self.advance();
+            }
+            self.expect("end of opening tag", b'>')?;
+            self.advance();
+
+            return Ok(LosslessNode::Element {
+                leading_trivia,
+                name,
+                attributes: attributes.into_boxed_slice(),
+                open_tag: &self.source[tag_start..self.current_index],
+                children: Box::new([]),
+                inner_content: "",
+                close_tag: "",
+            });
+        }
+
+        self.expect("end of opening tag", b'>')?;
+        self.advance();
+        let open_tag = &self.source[tag_start..self.current_index];
+
+        let inner_start = self.current_index;
+        let mut children = vec![];
+        if contains_ignore_ascii_case(&FOREIGN_ELEMENTS, name) {
+            let body_start = self.current_index;
+            self.foreign_text(name)?;
+            children.push(LosslessNode::Foreign {
+                leading_trivia: "",
+                raw: &self.source[body_start..self.current_index],
+            });
+        } else {
+            loop {
+                // Peek past any trailing whitespace to see if the closing tag
+                // follows; if so, keep that whitespace consumed so it ends up
+                // in `inner_content` instead of being mistaken for a child's
+                // leading trivia.
+                let lookahead_start = self.current_index;
+                self.ignore_whitespace();
+                if self.next_match(b"</") {
+                    // Trailing whitespace belongs in `inner_content`, not to
+                    // a child's leading trivia; leave it consumed.
+                    break;
+                }
+                self.current_index = lookahead_start;
+                if self.current().is_none() || self.peek(1).is_none() {
+                    return Err(self.err(format!("Expected matching closing tag for {}", name)));
+                }
+                children.push(self.lossless_node()?);
+            }
+        }
+        let inner_content = &self.source[inner_start..self.current_index];
+
+        let close_start = self.current_index;
+        // consume </
+        self.current_index += 2;
+        let closing_name = &self.source[self.consume_alphanumeric()?];
+        if !closing_name.eq_ignore_ascii_case(name) {
+            return Err(self.err(format!(
+                "Mismatched closing tag: Expected '{}', found '{}'",
+                name, closing_name
+            )));
+        }
+        self.ignore_whitespace();
+        self.expect("end of opening tag", b'>')?;
+        self.advance();
+        let close_tag = &self.source[close_start..self.current_index];
+
+        Ok(LosslessNode::Element {
+            leading_trivia,
+            name,
+            attributes: attributes.into_boxed_slice(),
+            open_tag,
+            children: children.into_boxed_slice(),
+            inner_content,
+            close_tag,
+        })
     }
 }
 
 // PARSING HELPERS
-impl<'a> ParseString<'a> {
+impl<'a, 'cfg> ParseString<'a, 'cfg> {
     /// Helper for the parser to know if it has reached the end of the string.
     fn is_at_end(&self) -> bool {
-        self.current_index >= self.source.as_bytes().len()
+        self.current_index >= self.source.len()
     }
 
     /// Helper to advance the current index and return character
@@ -96,22 +685,50 @@ impl<'a> ParseString<'a> {
             Some(control @ control_chars!()) => {
                 format!("[control character {:#x}]", control)
             }
+            Some(byte) if byte >= 0x80 => self
+                .current_char()
+                .map(|c| c.to_string())
+                .unwrap_or_else(|| format!("[invalid byte {:#x}]", byte)),
             Some(char) => (char as char).to_string(),
         }
     }
 
-    /// Helper to check whether the current character is alphanumeric
+    /// Helper to get the full Unicode scalar value starting at the current
+    /// byte offset, for the non-ASCII bytes `current()` only exposes one at a
+    /// time. Uses `get` rather than direct indexing so a cursor that ever
+    /// drifted off a char boundary would yield `None` instead of panicking.
+    fn current_char(&self) -> Option<char> {
+        self.source.get(self.current_index..)?.chars().next()
+    }
+
+    /// Helper to advance past the current character by its full UTF-8 width,
+    /// so a multibyte character is never split mid-codepoint. Only called
+    /// where `current_char()` is already known to be `Some` (from a prior
+    /// `current_is_alphanumeric()` check); the `None` arm is just a
+    /// byte-at-a-time fallback for EOF.
+    fn advance_char(&mut self) {
+        match self.current_char() {
+            Some(c) => self.current_index += c.len_utf8(),
+            None => self.advance(),
+        }
+    }
+
+    /// Helper to check whether the current character is alphanumeric.
+    /// Besides ASCII letters and digits, accepts non-ASCII identifier
+    /// characters (accented letters, CJK, etc.) since HTML, SVG and custom
+    /// elements allow Unicode in tag and attribute names.
     fn current_is_alphanumeric(&self) -> bool {
-        matches!(
-            self.current(),
-            Some(b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z')
-        )
+        match self.current() {
+            Some(b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z') => true,
+            Some(byte) if byte >= 0x80 => self.current_char().is_some_and(char::is_alphanumeric),
+            _ => false,
+        }
     }
 
     /// Helper to provide lookahead
     fn peek(&self, offset: usize) -> Option<u8> {
         let would_be_index = self.current_index + offset;
-        if would_be_index >= self.source.as_bytes().len() {
+        if would_be_index >= self.source.len() {
             return None;
         }
         Some(self.source.as_bytes()[would_be_index])
@@ -135,17 +752,30 @@ impl<'a> ParseString<'a> {
         }
     }
 
+    /// Case-insensitive counterpart to `next_match`. Compares raw bytes
+    /// rather than slicing `source` to a fixed width, so it can't panic by
+    /// landing mid-codepoint the way indexing `source[start..start + len]`
+    /// would if a multibyte character fell within that range.
+    fn next_match_ignore_ascii_case(&self, chars: &[u8]) -> bool {
+        match self.source.as_bytes().get(self.current_index..) {
+            Some(bytes) if bytes.len() >= chars.len() => {
+                bytes[..chars.len()].eq_ignore_ascii_case(chars)
+            }
+            _ => false,
+        }
+    }
+
     /// Helper to expect a specific character and error otherwise
     fn expect(&self, what: &str, char: u8) -> Result<(), InternalParseError> {
         if self.current_matches(char) {
             Ok(())
         } else {
-            Err(format!(
+            Err(self.err(format!(
                 "Expected {} '{}', found '{}'",
                 what,
                 char as char,
                 self.current_as_string()
-            ))
+            )))
         }
     }
 
@@ -159,140 +789,22 @@ impl<'a> ParseString<'a> {
     /// Helper to fully consume an alphanumeric range of characters, and return the resulting range to reference in a string
     fn consume_alphanumeric(&mut self) -> Result<Range<usize>, InternalParseError> {
         if !self.current_is_alphanumeric() {
-            return Err(format!(
+            return Err(self.err(format!(
                 "Expected alphanumeric, found '{}'",
                 self.current_as_string()
-            ));
+            )));
         }
 
         let starting_index = self.current_index;
         while self.current_is_alphanumeric() {
-            self.advance();
+            self.advance_char();
         }
         Ok(starting_index..self.current_index)
     }
 }
 
 // GRAMMAR IMPLEMENTATION
-impl<'a> ParseString<'a> {
-    /// Function to parse any kind of HTML node other than text.
-    fn strict_node(&mut self) -> NodeResult<'a> {
-        self.ignore_whitespace();
-
-        self.expect("start of a node", b'<')?;
-
-        match self.peek(1) {
-            None => Err(String::from("Expected something after start of node")),
-            Some(b'!') => {
-                // If there is a -, it is a comment
-                if let Some(b'-') = self.peek(2) {
-                    return self.comment();
-                }
-                // Otherwise attempt DOCTYPE
-                let decl = self.doctype_declaration();
-
-                match decl {
-                    Err(_) => Err(String::from("Expected doctype declaration or comment")),
-                    _ => decl,
-                }
-            } // doctype or comment
-            _ => self.element(), // element
-        }
-    }
-
-    /// Function to parse any kind of HTML node, including text.
-    fn node(&mut self) -> NodeResult<'a> {
-        if !self.current_matches(b'<') {
-            return self.text();
-        }
-
-        self.strict_node()
-    }
-
-    /// Function to parse regular HTML elements.
-    fn element(&mut self) -> NodeResult<'a> {
-        // consume <
-        self.advance();
-        // get tag name
-        let element_name = &self.source[self.consume_alphanumeric()?];
-
-        self.ignore_whitespace();
-
-        // parse attributes
-        let mut attributes: Vec<HTMLAttribute<'a>> = vec![];
-
-        while !self.current_matches(b'>') && !self.current_matches(b'/') {
-            let attribute = self.attribute()?;
-
-            if attributes.iter().any(|a| a.name == attribute.name) {
-                return Err(String::from(
-                    "Element has two attributes with the same name",
-                ));
-            }
-
-            attributes.push(attribute);
-            self.ignore_whitespace();
-        }
-
-        if contains_ignore_ascii_case(&VOID_ELEMENTS, element_name) {
-            // Void element, tag closer may optionally have a '/'
-            if self.current_matches(b'/') {
-                self.advance();
-            }
-            // consume >
-            self.expect("end of opening tag", b'>')?;
-            self.advance();
-
-            return Ok(HTMLNode::Element {
-                name: element_name,
-                attributes: attributes.into_boxed_slice(),
-                children: Box::new([]),
-            });
-        }
-
-        // Otherwise, not a node element, consume >
-        self.expect("end of opening tag", b'>')?;
-        self.advance();
-
-        let mut children = vec![];
-        if contains_ignore_ascii_case(&FOREIGN_ELEMENTS, element_name) {
-            children.push(self.foreign_text(element_name)?);
-        } else {
-            while !self.next_match(b"</") {
-                if self.current().is_none() || self.peek(1).is_none() {
-                    return Err(format!(
-                        "Expected matching closing tag for {}",
-                        element_name
-                    ));
-                }
-                children.push(self.node()?);
-            }
-        }
-
-        // Consume </
-        self.current_index += 2;
-
-        // Get closing element name and ensure it maches
-        let closing_tag_name = &self.source[self.consume_alphanumeric()?];
-
-        if !closing_tag_name.eq_ignore_ascii_case(element_name) {
-            return Err(format!(
-                "Mismatched closing tag: Expected '{}', found '{}'",
-                element_name, closing_tag_name
-            ));
-        }
-        self.ignore_whitespace();
-        // consume >
-        self.expect("end of opening tag", b'>')?;
-        self.advance();
-
-        Ok(HTMLNode::Element {
-            name: element_name,
-            attributes: attributes.into_boxed_slice(),
-            children: children.into_boxed_slice(),
-        })
-    }
-
+impl<'a, 'cfg> ParseString<'a, 'cfg> {
     fn attribute(&mut self) -> AttributeResult<'a> {
         // Match for element name
         let name_start = self.current_index;
@@ -303,28 +815,28 @@ impl<'a> ParseString<'a> {
             self.advance();
         }
         if self.current_index - name_start == 0 {
-            return Err(String::from("Expected attribute name"));
+            return Err(self.err("Expected attribute name"));
         }
         let name = &self.source[name_start..self.current_index];
 
         if let Some(control_chars!()) = self.current() {
-            return Err(format!(
+            return Err(self.err(format!(
                 "Unexpected control character {}",
                 self.current_as_string()
-            ));
+            )));
         }
 
         self.ignore_whitespace();
         if self.current().is_none() {
-            return Err(String::from("Expected something after attribute name"));
+            return Err(self.err("Expected something after attribute name"));
         }
 
-        let mut value = "";
+        let mut value = Cow::Borrowed("");
         if self.current_matches(b'=') {
             // consume =
             self.advance();
             match self.current() {
-                None => return Err(String::from("Expected attribute value after =")),
+                None => return Err(self.err("Expected attribute value after =")),
                 Some(quote @ (b'\'' | b'"')) => {
                     // Quoted attribute-value syntax
                     // consume opening quote
@@ -337,7 +849,7 @@ impl<'a> ParseString<'a> {
                     }
                     self.expect("value-ending quote", quote)?;
 
-                    value = &self.source[value_start..self.current_index];
+                    value = self.decode_entities(&self.source[value_start..self.current_index])?;
 
                     // consume closing quote
                     self.advance();
@@ -360,7 +872,7 @@ impl<'a> ParseString<'a> {
                     ) {
                         self.advance();
                     }
-                    value = &self.source[value_start..self.current_index];
+                    value = self.decode_entities(&self.source[value_start..self.current_index])?;
                 }
             }
         }
@@ -370,13 +882,18 @@ impl<'a> ParseString<'a> {
 
     /// Function to parse text nodes inside elements
     fn text(&mut self) -> NodeResult<'a> {
+        let raw = self.scan_text_raw();
+        Ok(HTMLNode::Text(self.decode_entities(raw)?))
+    }
+
+    /// Advances past a run of text (up to the next `<`, control character, or
+    /// EOF) and returns the raw, undecoded slice.
+    fn scan_text_raw(&mut self) -> &'a str {
         let starting_index = self.current_index;
         while !matches!(self.current(), Some(control_chars!() | b'<') | None) {
             self.advance();
         }
-        Ok(HTMLNode::Text(
-            &self.source[starting_index..self.current_index],
-        ))
+        &self.source[starting_index..self.current_index]
     }
 
     /// Function to parse foreign text, which will continue until it sees </element_name
@@ -401,12 +918,12 @@ impl<'a> ParseString<'a> {
             self.advance();
         }
         if self.current().is_none() {
-            return Err(format!("Expected closing tag </{element_name}>"));
+            return Err(self.err(format!("Expected closing tag </{element_name}>")));
         }
         // Return Foreign node with slice
-        return Ok(HTMLNode::Foreign(
+        Ok(HTMLNode::Foreign(
             &self.source[starting_index..self.current_index],
-        ));
+        ))
     }
 
     /// Function to parse a comment
@@ -419,7 +936,7 @@ impl<'a> ParseString<'a> {
         let starting_index = self.current_index;
 
         if self.next_match(b"->") || self.current_matches(b'-') {
-            return Err(String::from("Comments may not start with '>' or '->'"));
+            return Err(self.err("Comments may not start with '>' or '->'"));
         }
 
         while self.current().is_some() {
@@ -428,14 +945,14 @@ impl<'a> ParseString<'a> {
                 if self.peek(2) == Some(b'>') {
                     break;
                 } else {
-                    return Err(String::from("Comments may not contain '--'"));
+                    return Err(self.err("Comments may not contain '--'"));
                 }
             }
             // Otherwise consume
             self.advance();
         }
         if self.current().is_none() {
-            return Err(String::from("Expected comment tag closer '-->'"));
+            return Err(self.err("Expected comment tag closer '-->'"));
         }
         let comment_text = &self.source[starting_index..self.current_index];
         // Consume -->
@@ -446,14 +963,14 @@ impl<'a> ParseString<'a> {
 
     /// Function to parse a DOCYPE declaration
     fn doctype_declaration(&mut self) -> NodeResult<'a> {
-        // Check that DOCTYPE follows <!
-        if !self.source[(self.current_index + 2)..(self.current_index + 9)]
-            .eq_ignore_ascii_case("DOCTYPE")
-        {
-            return Err(String::new());
+        // Check that DOCTYPE follows <!. Compared on raw bytes, like
+        // `next_match`, rather than a fixed-width `&str` slice, which would
+        // need to land on a char boundary to avoid panicking.
+        if !self.next_match_ignore_ascii_case(b"<!DOCTYPE") {
+            return Err(self.err("Expected 'DOCTYPE' after '<!'"));
         }
         // Consume <!DOCTYPE
-        self.current_index += 9;
+        self.current_index += "<!DOCTYPE".len();
         self.ignore_whitespace();
 
         // This parser does not concern itself with actually parsing doctypes.
@@ -463,7 +980,7 @@ impl<'a> ParseString<'a> {
             self.advance();
         }
         if self.current().is_none() {
-            return Err(String::from("Expected DOCTYPE tag closer '>'"));
+            return Err(self.err("Expected DOCTYPE tag closer '>'"));
         }
         let doctype_string = &self.source[starting_index..self.current_index];
         // Consume >
@@ -471,4 +988,360 @@ impl<'a> ParseString<'a> {
 
         Ok(HTMLNode::Doctype(doctype_string))
     }
+
+    /// Function to parse a CDATA section: <![CDATA[ ... ]]>. Its content is
+    /// captured raw, up to the first ]]>, and is never entity-decoded.
+    fn cdata_section(&mut self) -> NodeResult<'a> {
+        // Consume <![CDATA[
+        self.current_index += "<![CDATA[".len();
+
+        let starting_index = self.current_index;
+        while !self.next_match(b"]]>") {
+            if self.current().is_none() {
+                return Err(self.err("Expected closing ']]>' for CDATA section"));
+            }
+            self.advance();
+        }
+        let content = &self.source[starting_index..self.current_index];
+        // Consume ]]>
+        self.current_index += "]]>".len();
+
+        Ok(HTMLNode::CData(content))
+    }
+
+    /// Function to parse a processing instruction: <?target data?>
+    fn processing_instruction(&mut self) -> NodeResult<'a> {
+        // Consume <?
+        self.current_index += 2;
+
+        let target = &self.source[self.consume_alphanumeric()?];
+
+        let data_start = self.current_index;
+        while !self.next_match(b"?>") {
+            if self.current().is_none() {
+                return Err(self.err("Expected closing '?>' for processing instruction"));
+            }
+            self.advance();
+        }
+        let data = &self.source[data_start..self.current_index];
+        // Consume ?>
+        self.current_index += 2;
+
+        Ok(HTMLNode::ProcessingInstruction { target, data })
+    }
+}
+
+// STREAMING EVENTS
+
+/// An element on `EventReader`'s open-element stack: just enough to detect
+/// its matching closing tag and, for foreign elements, to remember that the
+/// body hasn't been emitted as an event yet.
+struct OpenTag<'a> {
+    name: &'a str,
+    foreign_pending: bool,
+}
+
+/// Pull-parser returned by `parse_events()`: yields one `Event` per call
+/// instead of building a tree, driven by the same grammar functions as
+/// `parse()` but tracking open elements on an explicit stack instead of
+/// recursing, so no child `Vec`s are ever allocated.
+pub struct EventReader<'a, 'cfg> {
+    parser: ParseString<'a, 'cfg>,
+    stack: Vec<OpenTag<'a>>,
+    /// A second event already produced by the last pull, returned before any
+    /// further input is consumed. Used for a void element's `EndElement`
+    /// (which follows its `StartElement` with nothing in between).
+    pending: Option<Event<'a>>,
+    done: bool,
+}
+
+impl<'a, 'cfg> EventReader<'a, 'cfg> {
+    fn from_parser(parser: ParseString<'a, 'cfg>) -> Self {
+        EventReader {
+            parser,
+            stack: vec![],
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Pulls the next event. Follows the element grammar without
+    /// recursion: at the document root (empty stack), only
+    /// non-text constructs are accepted and surrounding whitespace is
+    /// insignificant, like the old `parse()`'s top-level loop; inside an
+    /// element, any closing tag ends its content regardless of whether it
+    /// names the right element (a mismatch raises an error rather than
+    /// searching for the right ancestor the way lenient mode does), like
+    /// the old `element()`'s children loop.
+    fn next_event(&mut self) -> Result<Event<'a>, InternalParseError> {
+        if let Some(event) = self.pending.take() {
+            return Ok(event);
+        }
+
+        if let Some(open) = self.stack.last_mut() {
+            if open.foreign_pending {
+                open.foreign_pending = false;
+                return match self.parser.foreign_text(open.name)? {
+                    HTMLNode::Foreign(raw) => Ok(Event::Foreign(raw)),
+                    _ => unreachable!("foreign_text() only ever returns Foreign"),
+                };
+            }
+        }
+
+        if self.stack.is_empty() {
+            if self.parser.is_at_end() {
+                return Ok(Event::Eof);
+            }
+            return self.open_construct();
+        }
+
+        if self.parser.next_match(b"</") {
+            return self.close_tag();
+        }
+
+        if self.parser.current().is_none() || self.parser.peek(1).is_none() {
+            let open = self.stack.last().unwrap();
+            return Err(self
+                .parser
+                .err(format!("Expected matching closing tag for {}", open.name)));
+        }
+
+        if self.parser.current_matches(b'<') {
+            return self.open_construct();
+        }
+
+        let raw = self.parser.scan_text_raw();
+        Ok(Event::Text(self.parser.decode_entities(raw)?))
+    }
+
+    /// Reads whatever follows a `<` that isn't a closing tag: a comment, a
+    /// DOCTYPE, a CDATA section, a processing instruction, or an element's
+    /// opening tag, same as the old `strict_node()`.
+    fn open_construct(&mut self) -> Result<Event<'a>, InternalParseError> {
+        self.parser.ignore_whitespace();
+        self.parser.expect("start of a node", b'<')?;
+
+        match self.parser.peek(1) {
+            None => Err(self.parser.err("Expected something after start of node")),
+            Some(b'!') => {
+                if self.parser.next_match(b"<![CDATA[") {
+                    return match self.parser.cdata_section()? {
+                        HTMLNode::CData(raw) => Ok(Event::CData(raw)),
+                        _ => unreachable!("cdata_section() only ever returns CData"),
+                    };
+                }
+                if self.parser.peek(2) == Some(b'-') {
+                    return match self.parser.comment()? {
+                        HTMLNode::Comment(raw) => Ok(Event::Comment(raw)),
+                        _ => unreachable!("comment() only ever returns Comment"),
+                    };
+                }
+                match self.parser.doctype_declaration() {
+                    Ok(HTMLNode::Doctype(raw)) => Ok(Event::Doctype(raw)),
+                    Ok(_) => unreachable!("doctype_declaration() only ever returns Doctype"),
+                    Err(_) => Err(self.parser.err("Expected doctype declaration or comment")),
+                }
+            }
+            Some(b'?') => match self.parser.processing_instruction()? {
+                HTMLNode::ProcessingInstruction { target, data } => {
+                    Ok(Event::ProcessingInstruction { target, data })
+                }
+                _ => unreachable!("processing_instruction() only ever returns ProcessingInstruction"),
+            },
+            _ => self.open_element(),
+        }
+    }
+
+    /// Reads an element's opening tag, same as the old `element()`, pushing
+    /// it onto the stack if it expects children and queuing its immediate
+    /// `EndElement` if it's void.
+    fn open_element(&mut self) -> Result<Event<'a>, InternalParseError> {
+        // consume <
+        self.parser.advance();
+        let name = &self.parser.source[self.parser.consume_alphanumeric()?];
+
+        self.parser.ignore_whitespace();
+
+        let mut attributes: Vec<HTMLAttribute<'a>> = vec![];
+        while !self.parser.current_matches(b'>') && !self.parser.current_matches(b'/') {
+            let attribute = self.parser.attribute()?;
+
+            if attributes.iter().any(|a| a.name == attribute.name) {
+                return Err(self.parser.err("Element has two attributes with the same name"));
+            }
+
+            attributes.push(attribute);
+            self.parser.ignore_whitespace();
+        }
+
+        if contains_ignore_ascii_case(&VOID_ELEMENTS, name) {
+            if self.parser.current_matches(b'/') {
+                self.parser.advance();
+            }
+            self.parser.expect("end of opening tag", b'>')?;
+            self.parser.advance();
+
+            self.pending = Some(Event::EndElement { name });
+            return Ok(Event::StartElement {
+                name,
+                attributes: attributes.into_boxed_slice(),
+            });
+        }
+
+        self.parser.expect("end of opening tag", b'>')?;
+        self.parser.advance();
+
+        self.stack.push(OpenTag {
+            name,
+            foreign_pending: contains_ignore_ascii_case(&FOREIGN_ELEMENTS, name),
+        });
+
+        Ok(Event::StartElement {
+            name,
+            attributes: attributes.into_boxed_slice(),
+        })
+    }
+
+    /// Reads a `</name>` closing tag and pops the stack, same as the old
+    /// `element()`'s closing-tag handling.
+    fn close_tag(&mut self) -> Result<Event<'a>, InternalParseError> {
+        let expected = self.stack.last().expect("only called with an open element").name;
+
+        // consume </
+        self.parser.current_index += 2;
+        let closing_name = &self.parser.source[self.parser.consume_alphanumeric()?];
+
+        if !closing_name.eq_ignore_ascii_case(expected) {
+            return Err(self.parser.err(format!(
+                "Mismatched closing tag: Expected '{}', found '{}'",
+                expected, closing_name
+            )));
+        }
+        self.parser.ignore_whitespace();
+        self.parser.expect("end of opening tag", b'>')?;
+        self.parser.advance();
+
+        let open = self.stack.pop().unwrap();
+        Ok(Event::EndElement { name: open.name })
+    }
 }
+
+impl<'a, 'cfg> Iterator for EventReader<'a, 'cfg> {
+    type Item = Result<Event<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        match self.next_event() {
+            Ok(Event::Eof) => {
+                self.done = true;
+                Some(Ok(Event::Eof))
+            }
+            Ok(event) => Some(Ok(event)),
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+// ENTITY DECODING
+impl<'a, 'cfg> ParseString<'a, 'cfg> {
+    /// Decodes named, decimal and hex character references in `raw`.
+    /// Returns a borrowed slice when no reference is present, and an owned
+    /// string otherwise. An unterminated or unrecognized reference raises an
+    /// error; in strict mode this aborts the parse, while lenient mode
+    /// (`lenient_text`) falls back to leaving the text undecoded.
+    fn decode_entities(&self, raw: &'a str) -> Result<Cow<'a, str>, InternalParseError> {
+        if !raw.contains('&') {
+            return Ok(Cow::Borrowed(raw));
+        }
+
+        let mut decoded = String::with_capacity(raw.len());
+        let mut rest = raw;
+        while let Some(amp_index) = rest.find('&') {
+            decoded.push_str(&rest[..amp_index]);
+            rest = &rest[amp_index..];
+
+            let (ch, consumed) = self.decode_one_reference(rest)?;
+            decoded.push(ch);
+            rest = &rest[consumed..];
+        }
+        decoded.push_str(rest);
+
+        Ok(Cow::Owned(decoded))
+    }
+
+    /// Decodes a single reference at the start of `s` (which must start with
+    /// '&'). Returns the decoded character and the number of bytes consumed.
+    fn decode_one_reference(&self, s: &str) -> Result<(char, usize), InternalParseError> {
+        let after_amp = &s[1..];
+
+        if let Some(after_hash) = after_amp.strip_prefix('#') {
+            if let Some(after_x) = after_hash
+                .strip_prefix('x')
+                .or_else(|| after_hash.strip_prefix('X'))
+            {
+                let end = after_x
+                    .find(';')
+                    .ok_or_else(|| self.err("Unterminated character reference"))?;
+                if end == 0 {
+                    return Err(self.err("Expected hex digits after '&#x'"));
+                }
+                let code_point = u32::from_str_radix(&after_x[..end], 16)
+                    .map_err(|_| self.err("Invalid hex character reference"))?;
+                return Ok((self.code_point_to_char(code_point)?, 3 + end + 1));
+            }
+
+            let end = after_hash
+                .find(';')
+                .ok_or_else(|| self.err("Unterminated character reference"))?;
+            if end == 0 {
+                return Err(self.err("Expected digits after '&#'"));
+            }
+            let code_point: u32 = after_hash[..end]
+                .parse()
+                .map_err(|_| self.err("Invalid decimal character reference"))?;
+            return Ok((self.code_point_to_char(code_point)?, 2 + end + 1));
+        }
+
+        let end = after_amp
+            .find(';')
+            .ok_or_else(|| self.err("Unterminated character reference"))?;
+        if end == 0 {
+            return Err(self.err("Expected a character reference name after '&'"));
+        }
+        let name = &after_amp[..end];
+        let ch = NAMED_ENTITIES
+            .binary_search_by(|(candidate, _)| (*candidate).cmp(name))
+            .map(|i| NAMED_ENTITIES[i].1)
+            .map_err(|_| self.err(format!("Unrecognized character reference '&{name};'")))?;
+
+        Ok((ch, 1 + end + 1))
+    }
+
+    /// Rejects code points outside the valid Unicode scalar range (surrogates
+    /// and anything past U+10FFFF), rather than silently substituting the
+    /// replacement character.
+    fn code_point_to_char(&self, code_point: u32) -> Result<char, InternalParseError> {
+        char::from_u32(code_point)
+            .ok_or_else(|| self.err(format!("Character reference '{code_point:#x}' is not a valid Unicode scalar value")))
+    }
+}
+
+/// A subset of the HTML5 named character references, sorted by name for binary search.
+const NAMED_ENTITIES: [(&str, char); 10] = [
+    ("amp", '&'),
+    ("apos", '\''),
+    ("copy", '\u{00A9}'),
+    ("gt", '>'),
+    ("lt", '<'),
+    ("mdash", '\u{2014}'),
+    ("nbsp", '\u{00A0}'),
+    ("ndash", '\u{2013}'),
+    ("quot", '"'),
+    ("reg", '\u{00AE}'),
+];
@@ -12,3 +12,17 @@ fn parse_example_doc() {
     ).unwrap().html()
     );
 }
+
+#[test]
+fn parses_unicode_element_and_attribute_names() {
+    dbg!(
+    parse(r#"<café 属性="値">こんにちは</café>"#).unwrap().html()
+    );
+}
+
+#[test]
+fn malformed_bang_with_multibyte_content_does_not_panic() {
+    // The 7-byte "DOCTYPE" keyword window lands mid-codepoint here (the
+    // second emoji's bytes straddle it); this should error, not panic.
+    assert!(parse("<!💥💥 not a doctype>").is_err());
+}
@@ -0,0 +1,151 @@
+use crate::ast::HTMLNode;
+use crate::parser::{contains_ignore_ascii_case, VOID_ELEMENTS};
+
+/// Callbacks invoked as `walk()` traverses a node tree depth-first, so
+/// downstream tools (minifiers, link extractors, ...) can traverse without
+/// re-matching `HTMLNode`'s variants by hand. Both default to doing
+/// nothing, so a visitor only needs to override what it cares about.
+pub trait Visitor {
+    /// Called when a node is reached, before an element's children (if any).
+    fn enter(&mut self, _node: &HTMLNode<'_>) {}
+    /// Called after a node, and for an element, after all its children.
+    fn leave(&mut self, _node: &HTMLNode<'_>) {}
+}
+
+/// Walks `nodes` depth-first, calling `visitor`'s `enter`/`leave` around
+/// each node and, for an element, its children in between. Iterative with
+/// an explicit stack, like the parser's own lenient mode, so a document
+/// with pathologically deep nesting can't overflow the call stack.
+pub fn walk<'a>(nodes: &[HTMLNode<'a>], visitor: &mut impl Visitor) {
+    enum Frame<'a, 'n> {
+        Enter(&'n HTMLNode<'a>),
+        Leave(&'n HTMLNode<'a>),
+    }
+
+    let mut stack: Vec<Frame<'a, '_>> = nodes.iter().rev().map(Frame::Enter).collect();
+    while let Some(frame) = stack.pop() {
+        match frame {
+            Frame::Enter(node) => {
+                visitor.enter(node);
+                stack.push(Frame::Leave(node));
+                if let HTMLNode::Element { children, .. } = node {
+                    stack.extend(children.iter().rev().map(Frame::Enter));
+                }
+            }
+            Frame::Leave(node) => visitor.leave(node),
+        }
+    }
+}
+
+/// Concatenates every `Text` node reachable from `nodes`. Foreign content
+/// (a `script`/`style`/etc. element's body) is parsed as `HTMLNode::Foreign`
+/// rather than `Text`, so it's skipped without any special-casing here.
+pub fn collect_text(nodes: &[HTMLNode<'_>]) -> String {
+    struct TextCollector(String);
+
+    impl Visitor for TextCollector {
+        fn enter(&mut self, node: &HTMLNode<'_>) {
+            if let HTMLNode::Text(text) = node {
+                self.0.push_str(text);
+            }
+        }
+    }
+
+    let mut collector = TextCollector(String::new());
+    walk(nodes, &mut collector);
+    collector.0
+}
+
+/// Writes `nodes` back out as well-formed HTML: attribute values are
+/// quoted and entity-escaped, void elements (per `VOID_ELEMENTS`) are
+/// written with no closing tag, and comments, the doctype, CDATA sections,
+/// processing instructions and foreign content are re-emitted verbatim.
+pub fn serialize(nodes: &[HTMLNode<'_>]) -> String {
+    struct Serializer(String);
+
+    impl Visitor for Serializer {
+        fn enter(&mut self, node: &HTMLNode<'_>) {
+            match node {
+                HTMLNode::Foreign(text) => self.0.push_str(text),
+                HTMLNode::Doctype(decl) => {
+                    self.0.push_str("<!DOCTYPE");
+                    if !decl.is_empty() {
+                        self.0.push(' ');
+                        self.0.push_str(decl);
+                    }
+                    self.0.push('>');
+                }
+                HTMLNode::Comment(text) => {
+                    self.0.push_str("<!--");
+                    self.0.push_str(text);
+                    self.0.push_str("-->");
+                }
+                HTMLNode::CData(text) => {
+                    self.0.push_str("<![CDATA[");
+                    self.0.push_str(text);
+                    self.0.push_str("]]>");
+                }
+                HTMLNode::ProcessingInstruction { target, data } => {
+                    self.0.push_str("<?");
+                    self.0.push_str(target);
+                    self.0.push_str(data);
+                    self.0.push_str("?>");
+                }
+                HTMLNode::Text(text) => escape_text(&mut self.0, text),
+                HTMLNode::Element {
+                    name, attributes, ..
+                } => {
+                    self.0.push('<');
+                    self.0.push_str(name);
+                    for attribute in attributes.iter() {
+                        self.0.push(' ');
+                        self.0.push_str(attribute.name);
+                        if !attribute.value.is_empty() {
+                            self.0.push_str("=\"");
+                            escape_attribute_value(&mut self.0, &attribute.value);
+                            self.0.push('"');
+                        }
+                    }
+                    self.0.push('>');
+                }
+            }
+        }
+
+        fn leave(&mut self, node: &HTMLNode<'_>) {
+            if let HTMLNode::Element { name, .. } = node {
+                if !contains_ignore_ascii_case(&VOID_ELEMENTS, name) {
+                    self.0.push_str("</");
+                    self.0.push_str(name);
+                    self.0.push('>');
+                }
+            }
+        }
+    }
+
+    let mut serializer = Serializer(String::new());
+    walk(nodes, &mut serializer);
+    serializer.0
+}
+
+/// Escapes the characters that would otherwise be ambiguous in HTML text.
+fn escape_text(out: &mut String, raw: &str) {
+    for ch in raw.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+/// Escapes the characters that would otherwise end a double-quoted
+/// attribute value early or be read back as a character reference.
+fn escape_attribute_value(out: &mut String, raw: &str) {
+    for ch in raw.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(ch),
+        }
+    }
+}